@@ -1,9 +1,311 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{parse_macro_input, Block, FnArg, Ident, ImplItem, ItemImpl, LitStr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use syn::{
+    parse::Parser, parse_macro_input, parse_quote, punctuated::Punctuated, token::Comma, Block,
+    FnArg, Ident, ImplItem, ItemFn, ItemImpl, Lit, LitStr, Meta, NestedMeta, ReturnType,
+};
 
-/// Generate a blocking method for each async method in an impl block. Supports either `tokio` or `async-std` backend.
-/// Generated methods are suffixed with `_blocking`.
+/// Parsed form of the `#[block_on(..)]` attribute, supporting both the plain
+/// `"tokio"` / `"async-std"` string and the richer `backend = "tokio", flavor =
+/// "current_thread", worker_threads = 4, fallible = true, timeout_ms = 5000`
+/// meta-list syntax.
+struct BlockOnArgs {
+    backend: String,
+    flavor: String,
+    worker_threads: Option<usize>,
+    fallible: bool,
+    timeout_ms: Option<u64>,
+}
+
+impl BlockOnArgs {
+    fn parse(attr: TokenStream) -> Self {
+        if let Ok(lit) = syn::parse::<LitStr>(attr.clone()) {
+            return BlockOnArgs {
+                backend: lit.value(),
+                flavor: "multi_thread".to_string(),
+                worker_threads: None,
+                fallible: false,
+                timeout_ms: None,
+            };
+        }
+
+        let args = Punctuated::<NestedMeta, Comma>::parse_terminated
+            .parse(attr)
+            .expect("`#[block_on]` expects either a bare backend string or `key = value` pairs");
+        let mut backend = None;
+        let mut flavor = "multi_thread".to_string();
+        let mut worker_threads = None;
+        let mut fallible = false;
+        let mut timeout_ms = None;
+
+        for arg in args {
+            if let NestedMeta::Meta(Meta::NameValue(nv)) = arg {
+                let name = nv.path.get_ident().map(Ident::to_string).unwrap_or_default();
+                match (name.as_str(), nv.lit) {
+                    ("backend", Lit::Str(s)) => backend = Some(s.value()),
+                    ("flavor", Lit::Str(s)) => flavor = s.value(),
+                    ("worker_threads", Lit::Int(i)) => {
+                        worker_threads = Some(
+                            i.base10_parse::<usize>()
+                                .expect("`worker_threads` must be a valid integer"),
+                        )
+                    }
+                    ("fallible", Lit::Bool(b)) => fallible = b.value,
+                    ("timeout_ms", Lit::Int(i)) => {
+                        timeout_ms = Some(
+                            i.base10_parse::<u64>()
+                                .expect("`timeout_ms` must be a valid integer"),
+                        )
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        BlockOnArgs {
+            backend: backend.expect("`#[block_on]` requires a `backend` when using the `key = value` syntax"),
+            flavor,
+            worker_threads,
+            fallible,
+            timeout_ms,
+        }
+    }
+}
+
+/// Wraps `expr` (a `block_on`/`block_in_place` call) in the `Ok(...)` that `fallible`
+/// mode needs, applying `?` first when `expr` itself already yields a `Result` (the
+/// case when `timeout_ms` is set and a `tokio::time::timeout`/`async_std::future::timeout`
+/// future was awaited). Non-fallible mode returns `expr` unchanged, whatever it yields.
+fn wrap_for_fallibility(expr: TokenStream2, args: &BlockOnArgs) -> TokenStream2 {
+    if !args.fallible {
+        return expr;
+    }
+    if args.timeout_ms.is_some() {
+        quote! { Ok(#expr?) }
+    } else {
+        quote! { Ok(#expr) }
+    }
+}
+
+/// Builds the body of a generated tokio `*_blocking` method/function. Detects an
+/// already-running runtime via `Handle::try_current` to avoid the "Cannot start a
+/// runtime from within a runtime" panic. If that runtime is multi-threaded, drives the
+/// future through `block_in_place`, which only works on that flavor; if it's
+/// `current_thread`, drives the future on a freshly spawned (and joined) OS thread
+/// instead, since `block_in_place` would itself panic there. With no active runtime at
+/// all, falls back to the shared static runtime, or (in `fallible` mode, where a failed
+/// `OnceLock` init couldn't be retried anyway) builds a fresh one per call via
+/// `Builder::build()?`. When `timeout_ms` is set, the call is wrapped in
+/// `tokio::time::timeout` inside an `async move` block so it's constructed lazily,
+/// once `block_on` has actually entered the runtime — `tokio::time::timeout` registers
+/// with the runtime's timer driver as soon as it's constructed, so building it eagerly
+/// as a plain `block_on` argument (evaluated before the runtime is entered) panics with
+/// "there is no reactor running" outside of an active runtime context.
+fn tokio_blocking_expr(call: TokenStream2, runtime_ident: &Ident, args: &BlockOnArgs) -> TokenStream2 {
+    let awaited_call = match args.timeout_ms {
+        Some(ms) => {
+            quote! { async move { tokio::time::timeout(std::time::Duration::from_millis(#ms), #call).await } }
+        }
+        None => call,
+    };
+
+    let multi_thread_branch =
+        wrap_for_fallibility(quote! { tokio::task::block_in_place(|| handle.block_on(#awaited_call)) }, args);
+
+    let own_runtime_branch = if args.fallible {
+        let builder_expr = runtime_builder_expr(args);
+        let blocked = wrap_for_fallibility(quote! { rt.block_on(#awaited_call) }, args);
+        quote! {
+            {
+                let rt = #builder_expr?;
+                #blocked
+            }
+        }
+    } else {
+        let builder_expr = runtime_builder_expr(args);
+        wrap_for_fallibility(
+            quote! { #runtime_ident.get_or_init(|| #builder_expr.unwrap()).block_on(#awaited_call) },
+            args,
+        )
+    };
+
+    // `block_in_place` panics with "can call blocking only when running on the
+    // multi-threaded runtime" if the active runtime is `current_thread`-flavored.
+    // In that case, drive the future on its own spawned OS thread (joined before
+    // returning) instead, which can block freely without touching the caller's
+    // single-threaded runtime.
+    let current_thread_branch = quote! {
+        std::thread::scope(|scope| scope.spawn(|| #own_runtime_branch).join().unwrap())
+    };
+
+    quote! {
+        {
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => match handle.runtime_flavor() {
+                    tokio::runtime::RuntimeFlavor::CurrentThread => #current_thread_branch,
+                    _ => #multi_thread_branch,
+                },
+                Err(_) => #own_runtime_branch,
+            }
+        }
+    }
+}
+
+/// Builds the body of a generated `*_blocking` function/method for the given backend,
+/// given the expression that invokes the original async function/method.
+fn blocking_body(attr: &str, call: TokenStream2, runtime_ident: &Ident, args: &BlockOnArgs) -> TokenStream2 {
+    if attr == "tokio" {
+        tokio_blocking_expr(call, runtime_ident, args)
+    } else if attr == "async-std" {
+        let awaited_call = match args.timeout_ms {
+            Some(ms) => {
+                quote! { async_std::future::timeout(std::time::Duration::from_millis(#ms), #call) }
+            }
+            None => call,
+        };
+        let blocked = wrap_for_fallibility(quote! { task::block_on(#awaited_call) }, args);
+        quote! {
+            {
+                use async_std::task;
+                #blocked
+            }
+        }
+    } else {
+        panic!("Only `tokio` and `async-std` backends are supported!")
+    }
+}
+
+/// Splits an async function/method's inputs into whether it has a `self` receiver
+/// and the list of patterns to forward as call arguments, shared by the `ItemImpl`
+/// and `ItemFn` code paths.
+fn receiver_and_call_args(
+    inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>,
+) -> (bool, Vec<Box<syn::Pat>>) {
+    let rec = inputs.iter().any(|arg| matches!(arg, FnArg::Receiver(_)));
+    let call_args = inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(arg) => Some(arg.pat.clone()),
+        })
+        .collect();
+    (rec, call_args)
+}
+
+/// Rewrites a method/function's declared return type `-> T` (or no return type at all)
+/// into `-> Result<T, Box<dyn std::error::Error + Send + Sync>>` for `fallible` mode.
+/// The error is `Send` because the `current_thread`-runtime path in
+/// `tokio_blocking_expr` joins this value back from a spawned thread, which requires
+/// the whole `Result` to be `Send`; it is also `Sync` because `?` only converts an
+/// error into `Box<dyn Error>` or `Box<dyn Error + Send + Sync>`, not into
+/// `Box<dyn Error + Send>`.
+fn fallible_return_type(output: &ReturnType) -> ReturnType {
+    let ty: syn::Type = match output {
+        ReturnType::Default => parse_quote! { () },
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    parse_quote! { -> Result<#ty, Box<dyn std::error::Error + Send + Sync>> }
+}
+
+/// Rewrites a method/function's declared return type `-> T` (or no return type at all)
+/// into `-> Result<T, Elapsed>` for `timeout_ms` mode when `fallible` isn't also set
+/// (which instead folds the timeout error into `Box<dyn std::error::Error + Send + Sync>`).
+fn timeout_return_type(output: &ReturnType, attr: &str) -> ReturnType {
+    let ty: syn::Type = match output {
+        ReturnType::Default => parse_quote! { () },
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    if attr == "tokio" {
+        parse_quote! { -> Result<#ty, tokio::time::error::Elapsed> }
+    } else {
+        parse_quote! { -> Result<#ty, async_std::future::TimeoutError> }
+    }
+}
+
+/// Process-wide counter handing out a distinct suffix to every `#[block_on]`
+/// invocation in a single compilation, so the generated runtime `static` never
+/// collides even when `unique_name` (the `Self` type or function name) repeats,
+/// e.g. two separate `#[block_on]` impls for the same type in one module.
+static RUNTIME_IDENT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Derives a unique identifier for the shared runtime `static` generated for a single
+/// `#[block_on]` invocation. `unique_name` (the `Self` type or function name) keeps the
+/// name readable in expanded output; the counter suffix guarantees it's actually unique.
+fn runtime_ident(unique_name: &str, span: proc_macro2::Span) -> Ident {
+    let sanitized: String = unique_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let n = RUNTIME_IDENT_COUNTER.fetch_add(1, Ordering::Relaxed);
+    Ident::new(
+        &format!("__BLOCK_ON_RUNTIME_{}_{}", sanitized.to_uppercase(), n),
+        span,
+    )
+}
+
+/// Builds a `Result<Runtime, std::io::Error>`-typed block expression that constructs a
+/// runtime honoring `flavor`/`worker_threads`, leaving error handling (`.unwrap()` vs
+/// `?`) to the caller.
+fn runtime_builder_expr(args: &BlockOnArgs) -> TokenStream2 {
+    let worker_threads_call = args
+        .worker_threads
+        .map(|n| quote! { builder.worker_threads(#n); });
+
+    let builder_init = if args.flavor == "current_thread" {
+        quote! { let mut builder = tokio::runtime::Builder::new_current_thread(); }
+    } else {
+        quote! { let mut builder = tokio::runtime::Builder::new_multi_thread(); }
+    };
+
+    quote! {
+        {
+            #builder_init
+            #worker_threads_call
+            builder.enable_all().build()
+        }
+    }
+}
+
+/// Builds the `static ...: OnceLock<Runtime>` item shared by every generated `*_blocking`
+/// function/method for a single `#[block_on]` invocation, honoring `flavor`/`worker_threads`.
+/// Left empty (`OnceLock::new()`) until the first call initializes it via `get_or_init` at
+/// the call site, so no dependency beyond `std` (stable since Rust 1.70) is required. Not
+/// emitted in `fallible` mode, where each call builds its own runtime instead.
+fn runtime_static(attr: &str, args: &BlockOnArgs, needed: bool, runtime_ident: &Ident) -> TokenStream2 {
+    if attr != "tokio" || !needed || args.fallible {
+        return quote! {};
+    }
+
+    quote! {
+        static #runtime_ident: std::sync::OnceLock<tokio::runtime::Runtime> = std::sync::OnceLock::new();
+    }
+}
+
+/// Generate a blocking method for each async method in an impl block, or a sibling
+/// blocking function for a free `async fn`. Supports either `tokio` or `async-std`
+/// backend. Generated methods/functions are suffixed with `_blocking`.
+///
+/// The attribute accepts either the plain backend string (`"tokio"` or `"async-std"`)
+/// or a `key = value` list naming the `backend` plus the `fallible` and `timeout_ms`
+/// options described below and, for the `tokio` backend, the runtime flavor and
+/// worker-thread count:
+///
+/// ```
+/// use block_on_proc::block_on;
+///
+/// struct Tokio {}
+///
+/// #[block_on(backend = "tokio", flavor = "current_thread", worker_threads = 4)]
+/// impl Tokio {
+///     async fn test_async(&self) {}
+/// }
+/// ```
+///
+/// `flavor` is either `"current_thread"` or `"multi_thread"` (the default, matching
+/// the plain-string form). `worker_threads` is only meaningful for `"multi_thread"`
+/// and is forwarded to `Builder::worker_threads`.
 ///
 /// # Example `tokio`
 /// ```
@@ -22,15 +324,44 @@ use syn::{parse_macro_input, Block, FnArg, Ident, ImplItem, ItemImpl, LitStr};
 /// # struct Dummy {}
 /// # impl Dummy {
 /// async fn test_async(&self) {}
-///         
+///
 /// fn test_async_blocking(&self) {
-///     use tokio::runtime::Runtime;
-///     let mut rt = Runtime::new().unwrap();
-///     rt.block_on(self.test_async())
+///     static BLOCK_ON_RUNTIME: std::sync::OnceLock<tokio::runtime::Runtime> = std::sync::OnceLock::new();
+///     match tokio::runtime::Handle::try_current() {
+///         Ok(handle) => match handle.runtime_flavor() {
+///             tokio::runtime::RuntimeFlavor::CurrentThread => std::thread::scope(|scope| {
+///                 scope
+///                     .spawn(|| {
+///                         BLOCK_ON_RUNTIME
+///                             .get_or_init(|| tokio::runtime::Runtime::new().unwrap())
+///                             .block_on(self.test_async())
+///                     })
+///                     .join()
+///                     .unwrap()
+///             }),
+///             _ => tokio::task::block_in_place(|| handle.block_on(self.test_async())),
+///         },
+///         Err(_) => BLOCK_ON_RUNTIME
+///             .get_or_init(|| tokio::runtime::Runtime::new().unwrap())
+///             .block_on(self.test_async()),
+///     }
 /// }
 /// # }
 /// ```
 ///
+/// The runtime is built once per `#[block_on]` invocation, on first use, and shared by
+/// every `*_blocking` method/function it generates, instead of being rebuilt on each
+/// call (except in `fallible` mode, where each call builds its own runtime). It's a plain `std::sync::OnceLock`, so using `#[block_on]` never pulls in a
+/// dependency beyond `std`.
+/// (The static's real name is derived from the `Self` type or function name plus a
+/// per-invocation counter, so that multiple `#[block_on]` items in the same module
+/// never collide, even two separate `#[block_on]` impls for the same type.) If the
+/// caller is already inside a tokio runtime, the generated method detects the active
+/// `Handle` instead of panicking with "Cannot start a runtime from within a runtime".
+/// On a multi-threaded runtime it drives the future through `block_in_place`; on a
+/// `current_thread` runtime (where `block_in_place` itself panics) it instead drives
+/// the future on a freshly spawned, joined OS thread.
+///
 /// # Example `async-std`
 /// ```
 /// use block_on_proc::block_on;
@@ -47,7 +378,7 @@ use syn::{parse_macro_input, Block, FnArg, Ident, ImplItem, ItemImpl, LitStr};
 /// ```no_run
 /// # struct Dummy {}
 /// # impl Dummy {
-/// async fn test_async(&self) {}        
+/// async fn test_async(&self) {}
 ///
 /// fn test_async_blocking(&self) {
 ///       use async_std::task;
@@ -55,18 +386,99 @@ use syn::{parse_macro_input, Block, FnArg, Ident, ImplItem, ItemImpl, LitStr};
 /// }
 /// # }
 /// ```
+///
+/// # Example free function
+///
+/// `#[block_on]` can also be placed on a free `async fn`, generating a sibling
+/// blocking function next to it instead of requiring an impl block:
+///
+/// ```
+/// use block_on_proc::block_on;
+///
+/// #[block_on("tokio")]
+/// async fn test_async() -> u8 {
+///     1
+/// }
+///
+/// let result = test_async_blocking();
+/// ```
+///
+/// # Example fallible
+///
+/// Passing `fallible = true` changes the declared return type from `T` to
+/// `Result<T, Box<dyn std::error::Error + Send + Sync>>` and propagates runtime
+/// construction failures with `?` instead of `.unwrap()`-ing them:
+///
+/// ```
+/// use block_on_proc::block_on;
+///
+/// struct Tokio {}
+///
+/// #[block_on(backend = "tokio", fallible = true)]
+/// impl Tokio {
+///     async fn test_async(&self) -> u8 {
+///         1
+///     }
+/// }
+///
+/// let tokio = Tokio {};
+/// let result: Result<u8, Box<dyn std::error::Error + Send + Sync>> = tokio.test_async_blocking();
+/// ```
+///
+/// # Example timeout
+///
+/// `timeout_ms` wraps the awaited future in `tokio::time::timeout` (or
+/// `async_std::future::timeout`), turning the declared return type into
+/// `Result<T, tokio::time::error::Elapsed>` so a hung future doesn't block forever:
+///
+/// ```
+/// use block_on_proc::block_on;
+///
+/// struct Tokio {}
+///
+/// #[block_on(backend = "tokio", timeout_ms = 5000)]
+/// impl Tokio {
+///     async fn test_async(&self) -> u8 {
+///         1
+///     }
+/// }
+///
+/// let tokio = Tokio {};
+/// let result: Result<u8, tokio::time::error::Elapsed> = tokio.test_async_blocking();
+/// ```
+///
+/// Combined with `fallible = true`, the `Elapsed`/`TimeoutError` is folded into the
+/// same `Box<dyn std::error::Error + Send + Sync>` used for runtime construction
+/// failures.
 
 #[proc_macro_attribute]
 pub fn block_on(attr: TokenStream, tokens: TokenStream) -> TokenStream {
-    let attr = parse_macro_input!(attr as LitStr).value();
+    let args = BlockOnArgs::parse(attr);
+
+    if syn::parse::<ItemImpl>(tokens.clone()).is_ok() {
+        block_on_impl(args, tokens)
+    } else if syn::parse::<ItemFn>(tokens.clone()).is_ok() {
+        block_on_fn(args, tokens)
+    } else {
+        panic!("`#[block_on]` can only be applied to an impl block or a free `async fn`")
+    }
+}
 
-    let orig_tokens = tokens.clone();
+/// `#[block_on]` applied to an `impl` block: generates a `*_blocking` method next to
+/// every async method in the block.
+fn block_on_impl(args: BlockOnArgs, tokens: TokenStream) -> TokenStream {
+    let attr = args.backend.as_str();
 
-    let in_impl = parse_macro_input!(orig_tokens as ItemImpl);
+    let in_impl = parse_macro_input!(tokens as ItemImpl);
     let strct = in_impl.self_ty.clone();
     let mut orig_impl = in_impl.clone();
-    let mut out_impl = in_impl.clone();
-    out_impl.items = Vec::new();
+
+    let rt_ident = runtime_ident(
+        &quote! { #strct }.to_string(),
+        proc_macro2::Span::call_site(),
+    );
+
+    let mut has_blocking_method = false;
 
     for item in in_impl.items {
         match item {
@@ -77,71 +489,29 @@ pub fn block_on(attr: TokenStream, tokens: TokenStream) -> TokenStream {
                     continue;
                 }
                 out_method.sig.asyncness = None;
+                has_blocking_method = true;
 
                 out_method.sig.ident = Ident::new(
                     &format!("{}_blocking", method.sig.ident.to_string()),
                     method.sig.ident.span(),
                 );
 
-                let inputs = &method.sig.inputs;
-
-                let rec = inputs.into_iter().any(|arg| match arg {
-                    FnArg::Receiver(_) => true,
-                    FnArg::Typed(_) => false,
-                });
-
-                let call_args = inputs
-                    .into_iter()
-                    .map(|arg| match arg {
-                        FnArg::Receiver(_) => None,
-                        FnArg::Typed(arg) => Some(arg.pat.clone()),
-                    })
-                    .filter(|pat| pat.is_some())
-                    .map(|arg| arg.unwrap());
-
-                let block_proc2 = if rec {
-                    {
-                        if attr == "tokio" {
-                            quote! {
-                                    {
-                                        use tokio::runtime::Runtime;
-                                        let mut rt = Runtime::new().unwrap();
-                                        rt.block_on(self.#name(#(#call_args),*))
-                                    }
-                            }
-                        } else if attr == "async-std" {
-                            quote! {
-                                    {
-                                        use async_std::task;
-                                        task::block_on(self.#name(#(#call_args),*))
-                                    }
-                            }
-                        } else {
-                            panic!("Only `tokio` and `async-std` backends are supported!")
-                        }
-                    }
+                if args.fallible {
+                    out_method.sig.output = fallible_return_type(&method.sig.output);
+                } else if args.timeout_ms.is_some() {
+                    out_method.sig.output = timeout_return_type(&method.sig.output, attr);
+                }
+
+                let (rec, call_args) = receiver_and_call_args(&method.sig.inputs);
+
+                let call = if rec {
+                    quote! { self.#name(#(#call_args),*) }
                 } else {
-                    if attr == "tokio" {
-                        quote! {
-                                {
-                                    use tokio::runtime::Runtime;
-                                    let mut rt = Runtime::new().unwrap();
-                                    rt.block_on(#strct::#name(#(#call_args),*))
-                                }
-                        }
-                    } else if attr == "async-std" {
-                        quote! {
-                                {
-                                    use async_std::task;
-                                    task::block_on(#strct::#name(#(#call_args),*))
-                                }
-                        }
-                    } else {
-                        panic!("Only `tokio` and `async-std` backends are supported!")
-                    }
+                    quote! { #strct::#name(#(#call_args),*) }
                 };
 
-                let block_proc = proc_macro::TokenStream::from(block_proc2);
+                let block_proc =
+                    proc_macro::TokenStream::from(blocking_body(attr, call, &rt_ident, &args));
                 out_method.block = parse_macro_input!(block_proc as Block);
                 orig_impl.items.push(ImplItem::Method(out_method));
             }
@@ -149,14 +519,59 @@ pub fn block_on(attr: TokenStream, tokens: TokenStream) -> TokenStream {
         }
     }
 
-    // Returns generated tokens
+    // A single process-wide runtime shared by every `*_blocking` method generated
+    // for this impl block, built lazily on first use instead of per call.
+    let runtime_item = runtime_static(attr, &args, has_blocking_method, &rt_ident);
+
     let out = quote! {
+        #runtime_item
+
         #orig_impl
     };
 
     out.into()
 }
 
+/// `#[block_on]` applied to a free `async fn`: generates a sibling `*_blocking` fn.
+fn block_on_fn(args: BlockOnArgs, tokens: TokenStream) -> TokenStream {
+    let attr = args.backend.as_str();
+
+    let orig_fn = parse_macro_input!(tokens as ItemFn);
+    let name = &orig_fn.sig.ident;
+    let mut out_fn = orig_fn.clone();
+    out_fn.sig.asyncness = None;
+    out_fn.sig.ident = Ident::new(
+        &format!("{}_blocking", orig_fn.sig.ident.to_string()),
+        orig_fn.sig.ident.span(),
+    );
+
+    if args.fallible {
+        out_fn.sig.output = fallible_return_type(&orig_fn.sig.output);
+    } else if args.timeout_ms.is_some() {
+        out_fn.sig.output = timeout_return_type(&orig_fn.sig.output, attr);
+    }
+
+    let rt_ident = runtime_ident(&name.to_string(), name.span());
+
+    let (_, call_args) = receiver_and_call_args(&orig_fn.sig.inputs);
+    let call = quote! { #name(#(#call_args),*) };
+
+    let block_proc = proc_macro::TokenStream::from(blocking_body(attr, call, &rt_ident, &args));
+    out_fn.block = Box::new(parse_macro_input!(block_proc as Block));
+
+    let runtime_item = runtime_static(attr, &args, true, &rt_ident);
+
+    let out = quote! {
+        #runtime_item
+
+        #orig_fn
+
+        #out_fn
+    };
+
+    out.into()
+}
+
 #[test]
 fn ui() {
     let t = trybuild::TestCases::new();
@@ -0,0 +1,149 @@
+use block_on_proc::block_on;
+
+struct Tokio {}
+
+#[block_on("tokio")]
+impl Tokio {
+    async fn test_async(&self) -> u8 {
+        1
+    }
+}
+
+struct AsyncStd {}
+
+#[block_on("async-std")]
+impl AsyncStd {
+    async fn test_async(&self) -> u8 {
+        1
+    }
+}
+
+struct CurrentThread {}
+
+#[block_on(backend = "tokio", flavor = "current_thread")]
+impl CurrentThread {
+    async fn test_async(&self) -> u8 {
+        1
+    }
+}
+
+struct MultiThreadFixed {}
+
+#[block_on(backend = "tokio", flavor = "multi_thread", worker_threads = 2)]
+impl MultiThreadFixed {
+    async fn test_async(&self) -> u8 {
+        1
+    }
+}
+
+struct RuntimeReuse {}
+
+#[block_on(backend = "tokio", flavor = "multi_thread", worker_threads = 1)]
+impl RuntimeReuse {
+    async fn worker_thread_id(&self) -> std::thread::ThreadId {
+        // `Runtime::block_on` polls this future on the calling thread, so spawn a
+        // task to observe the thread of the runtime's own worker.
+        tokio::spawn(async { std::thread::current().id() })
+            .await
+            .unwrap()
+    }
+}
+
+struct Nested {}
+
+#[block_on("tokio")]
+impl Nested {
+    async fn test_async(&self) -> u8 {
+        1
+    }
+}
+
+// Calling a generated `*_blocking` method from inside an already-running tokio
+// runtime must not panic with "Cannot start a runtime from within a runtime".
+#[tokio::main]
+async fn call_from_async_context() -> u8 {
+    let nested = Nested {};
+    nested.test_async_blocking()
+}
+
+// Same as above, but the ambient runtime is `current_thread`-flavored, where
+// `block_in_place` itself would panic ("can call blocking only when running on
+// the multi-threaded runtime") instead of the nested-runtime panic.
+#[tokio::main(flavor = "current_thread")]
+async fn call_from_current_thread_async_context() -> u8 {
+    let nested = Nested {};
+    nested.test_async_blocking()
+}
+
+#[block_on("tokio")]
+async fn free_async_fn() -> u8 {
+    1
+}
+
+struct Fallible {}
+
+#[block_on(backend = "tokio", fallible = true)]
+impl Fallible {
+    async fn test_async(&self) -> u8 {
+        1
+    }
+}
+
+#[block_on(backend = "async-std", fallible = true)]
+async fn fallible_free_fn() -> u8 {
+    1
+}
+
+struct WithTimeout {}
+
+#[block_on(backend = "tokio", timeout_ms = 5000)]
+impl WithTimeout {
+    async fn test_async(&self) -> u8 {
+        1
+    }
+}
+
+#[block_on(backend = "tokio", fallible = true, timeout_ms = 5000)]
+async fn fallible_with_timeout() -> u8 {
+    1
+}
+
+fn main() {
+    assert_eq!(free_async_fn_blocking(), 1);
+
+    let fallible = Fallible {};
+    assert_eq!(fallible.test_async_blocking().unwrap(), 1);
+    assert_eq!(fallible_free_fn_blocking().unwrap(), 1);
+
+    let with_timeout = WithTimeout {};
+    assert_eq!(with_timeout.test_async_blocking().unwrap(), 1);
+    assert_eq!(fallible_with_timeout_blocking().unwrap(), 1);
+
+    assert_eq!(call_from_async_context(), 1);
+    assert_eq!(call_from_current_thread_async_context(), 1);
+
+    let tokio = Tokio {};
+    assert_eq!(tokio.test_async_blocking(), 1);
+    assert_eq!(tokio.test_async_blocking(), 1);
+
+    // With a single worker thread, the spawned task can only land on the same
+    // OS thread across calls if the runtime (and its one worker) is actually
+    // reused rather than rebuilt (and re-spawned) on every call.
+    let runtime_reuse = RuntimeReuse {};
+    let first_thread = runtime_reuse.worker_thread_id_blocking();
+    let second_thread = runtime_reuse.worker_thread_id_blocking();
+    assert_eq!(
+        first_thread, second_thread,
+        "blocking calls must reuse the same process-wide runtime, not rebuild a new one each time"
+    );
+
+    let async_std = AsyncStd {};
+    assert_eq!(async_std.test_async_blocking(), 1);
+    assert_eq!(async_std.test_async_blocking(), 1);
+
+    let current_thread = CurrentThread {};
+    assert_eq!(current_thread.test_async_blocking(), 1);
+
+    let multi_thread_fixed = MultiThreadFixed {};
+    assert_eq!(multi_thread_fixed.test_async_blocking(), 1);
+}